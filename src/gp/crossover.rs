@@ -11,6 +11,17 @@ enum CrossoverMode {
     /// Corresponds to `Crossover::one_point_leaf_biased`.
     OnePointLeafBiased(f32),
     HardPrune(usize),
+    /// Corresponds to `Crossover::size_fair`.
+    SizeFair,
+    /// Corresponds to `Crossover::common_region`.
+    CommonRegion,
+    /// Corresponds to `Crossover::koza`.
+    Koza {
+        internal_pb: f32,
+        external_pb: f32,
+        max_depth: usize,
+        keep_trying: usize,
+    },
 }
 
 /// Configures crossover (mating) between GP individuals.
@@ -53,6 +64,51 @@ impl Crossover {
         }
     }
 
+    /// Get an operator to perform size-fair one-point crossover between two individuals.
+    ///
+    /// A random crossover point is chosen in `indv1` as usual, but the point in `indv2`
+    /// is chosen from among nodes whose subtree size is close to that of the chosen
+    /// `indv1` subtree, which keeps swapped subtrees comparably sized and helps curb bloat.
+    pub fn size_fair() -> Crossover {
+        Crossover {
+            mode: CrossoverMode::SizeFair,
+        }
+    }
+
+    /// Get an operator to perform common-region (homologous) one-point crossover.
+    ///
+    /// Both trees are walked simultaneously from their roots; whenever the two nodes
+    /// being compared have a different number of children, both of their subtrees are
+    /// skipped entirely. The surviving, structurally aligned positions form the "common
+    /// region", and a single paired position from it is swapped. This keeps crossover
+    /// well-behaved for strongly-typed or fixed-arity node sets.
+    pub fn common_region() -> Crossover {
+        Crossover {
+            mode: CrossoverMode::CommonRegion,
+        }
+    }
+
+    /// Get an operator performing Koza-style 90/10 internal/external node crossover.
+    ///
+    /// With probability `internal_pb` the chosen crossover point in each parent is an
+    /// internal (non-terminal) node, otherwise a terminal, selected uniformly among
+    /// nodes of that category. `external_pb` is kept alongside `internal_pb` for callers
+    /// that want to express the pair as summing to 1 (e.g. Koza's classic 0.9/0.1 split),
+    /// though only `internal_pb` is consulted. The swap is reverted and retried with
+    /// freshly chosen points if it would make either tree exceed `max_depth`, up to
+    /// `keep_trying` attempts (`0` means retry without limit); if every attempt fails,
+    /// the parents are left unchanged.
+    pub fn koza(internal_pb: f32, external_pb: f32, max_depth: usize, keep_trying: usize) -> Crossover {
+        Crossover {
+            mode: CrossoverMode::Koza {
+                internal_pb,
+                external_pb,
+                max_depth,
+                keep_trying,
+            },
+        }
+    }
+
     /// Crossover (mate) two individuals according to the configured crossover mode.
     pub fn mate<T, R>(&self, indv1: &mut Individual<T>, indv2: &mut Individual<T>, rng: &mut R)
     where
@@ -67,6 +123,14 @@ impl Crossover {
             CrossoverMode::HardPrune(max_depth) => {
                 self.mate_hard_prune(indv1, indv2, max_depth, rng)
             }
+            CrossoverMode::SizeFair => self.mate_size_fair(indv1, indv2, rng),
+            CrossoverMode::CommonRegion => self.mate_common_region(indv1, indv2, rng),
+            CrossoverMode::Koza {
+                internal_pb,
+                external_pb: _,
+                max_depth,
+                keep_trying,
+            } => self.mate_koza(indv1, indv2, internal_pb, max_depth, keep_trying, rng),
         }
     }
 
@@ -115,24 +179,81 @@ impl Crossover {
         let leaf = rng.gen_bool(f64::from(bias));
 
         let target_index1 = rng.gen_range(0, indv1.nodes_count());
-        let mut target_index2 = rng.gen_range(0, indv2.nodes_count());
-        let mut node_counter = 0;
+        // Pick uniformly among nodes of the chosen category, rather than drawing an
+        // index over all of indv2 and walking it down: indv2 may not have that many
+        // matching nodes, and the walk-it-down approach underflows in that case.
+        let candidates2 = category_indices(&mut indv2.tree, !leaf);
+        if candidates2.is_empty() {
+            return;
+        }
+        let target_index2 = candidates2[rng.gen_range(0, candidates2.len())];
+
         indv1.tree.map_while(|node1, index1, _| {
             if index1 == target_index1 {
-                indv2.tree.map_while(|node2, _, _| {
-                    let is_leaf = node2.count_children() == 0;
-                    if is_leaf != leaf {
-                        target_index2 -= 1;
+                indv2.tree.map_while(|node2, index2, _| {
+                    if index2 == target_index2 {
+                        mem::swap(node1, node2);
+                        false
+                    } else {
                         true
+                    }
+                });
+                false
+            } else {
+                true
+            }
+        });
+
+        indv1.recalculate_metadata();
+        indv2.recalculate_metadata();
+    }
+    fn mate_size_fair<T, R>(
+        &self,
+        indv1: &mut Individual<T>,
+        indv2: &mut Individual<T>,
+        rng: &mut R,
+    ) where
+        T: Tree,
+        R: Rng,
+    {
+        let target_index1 = rng.gen_range(0, indv1.nodes_count());
+
+        let mut avg = 1;
+        indv1.tree.map_while(|node1, index1, _| {
+            if index1 == target_index1 {
+                avg = node1.count_nodes();
+                false
+            } else {
+                true
+            }
+        });
+
+        let sigma = if avg > 1 { (avg - 1) as i64 } else { 1 };
+        let target_size = {
+            let delta = rng.gen_range(-sigma, sigma + 1);
+            let size = avg as i64 + delta;
+            if size < 1 { 1 } else { size as usize }
+        };
+
+        let sizes2 = indv2.subtree_sizes();
+        let max_size = sizes2.iter().cloned().max().unwrap_or(1);
+        let mut buckets: Vec<Vec<usize>> = vec![Vec::new(); max_size + 1];
+        for (index2, &size) in sizes2.iter().enumerate() {
+            buckets[size].push(index2);
+        }
+
+        let bucket_size = pick_fair_bucket(&buckets, target_size);
+        let candidates = &buckets[bucket_size];
+        let target_index2 = candidates[rng.gen_range(0, candidates.len())];
+
+        indv1.tree.map_while(|node1, index1, _| {
+            if index1 == target_index1 {
+                indv2.tree.map_while(|node2, index2, _| {
+                    if index2 == target_index2 {
+                        mem::swap(node1, node2);
+                        false
                     } else {
-                        let ret = if node_counter == target_index2 {
-                            mem::swap(node1, node2);
-                            false
-                        } else {
-                            true
-                        };
-                        node_counter += 1;
-                        ret
+                        true
                     }
                 });
                 false
@@ -140,7 +261,155 @@ impl Crossover {
                 true
             }
         });
+
+        indv1.recalculate_metadata();
+        indv2.recalculate_metadata();
     }
+
+    fn mate_common_region<T, R>(
+        &self,
+        indv1: &mut Individual<T>,
+        indv2: &mut Individual<T>,
+        rng: &mut R,
+    ) where
+        T: Tree,
+        R: Rng,
+    {
+        let mut pairs = Vec::new();
+        common_region(&indv1.tree, &indv2.tree, &mut 0, &mut 0, &mut pairs);
+        let (target_index1, target_index2) = pairs[rng.gen_range(0, pairs.len())];
+
+        indv1.tree.map_while(|node1, index1, _| {
+            if index1 == target_index1 {
+                indv2.tree.map_while(|node2, index2, _| {
+                    if index2 == target_index2 {
+                        mem::swap(node1, node2);
+                        false
+                    } else {
+                        true
+                    }
+                });
+                false
+            } else {
+                true
+            }
+        });
+
+        indv1.recalculate_metadata();
+        indv2.recalculate_metadata();
+    }
+
+    fn mate_koza<T, R>(
+        &self,
+        indv1: &mut Individual<T>,
+        indv2: &mut Individual<T>,
+        internal_pb: f32,
+        max_depth: usize,
+        keep_trying: usize,
+        rng: &mut R,
+    ) where
+        T: Tree,
+        R: Rng,
+    {
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+            let want_internal = rng.gen_bool(f64::from(internal_pb));
+
+            let candidates1 = category_indices(&mut indv1.tree, want_internal);
+            let candidates2 = category_indices(&mut indv2.tree, want_internal);
+            if candidates1.is_empty() || candidates2.is_empty() {
+                if keep_trying != 0 && attempts >= keep_trying {
+                    return;
+                }
+                continue;
+            }
+            let target_index1 = candidates1[rng.gen_range(0, candidates1.len())];
+            let target_index2 = candidates2[rng.gen_range(0, candidates2.len())];
+
+            let mut depth1 = 0;
+            indv1.tree.map_while(|_, index1, depth| {
+                if index1 == target_index1 {
+                    depth1 = depth;
+                    false
+                } else {
+                    true
+                }
+            });
+            let mut depth2 = 0;
+            indv2.tree.map_while(|_, index2, depth| {
+                if index2 == target_index2 {
+                    depth2 = depth;
+                    false
+                } else {
+                    true
+                }
+            });
+
+            indv1.tree.map_while(|node1, index1, _| {
+                if index1 == target_index1 {
+                    indv2.tree.map_while(|node2, index2, _| {
+                        if index2 == target_index2 {
+                            mem::swap(node1, node2);
+                            false
+                        } else {
+                            true
+                        }
+                    });
+                    false
+                } else {
+                    true
+                }
+            });
+
+            let mut height1 = 0;
+            indv1.tree.map_while(|node, index1, _| {
+                if index1 == target_index1 {
+                    height1 = subtree_height(node);
+                    false
+                } else {
+                    true
+                }
+            });
+            let mut height2 = 0;
+            indv2.tree.map_while(|node, index2, _| {
+                if index2 == target_index2 {
+                    height2 = subtree_height(node);
+                    false
+                } else {
+                    true
+                }
+            });
+
+            if depth1 + height1 <= max_depth && depth2 + height2 <= max_depth {
+                indv1.recalculate_metadata();
+                indv2.recalculate_metadata();
+                return;
+            }
+
+            // revert: swapping the same pair of positions back undoes the mutation.
+            indv1.tree.map_while(|node1, index1, _| {
+                if index1 == target_index1 {
+                    indv2.tree.map_while(|node2, index2, _| {
+                        if index2 == target_index2 {
+                            mem::swap(node1, node2);
+                            false
+                        } else {
+                            true
+                        }
+                    });
+                    false
+                } else {
+                    true
+                }
+            });
+
+            if keep_trying != 0 && attempts >= keep_trying {
+                return;
+            }
+        }
+    }
+
     fn mate_hard_prune<T, R>(
         &self,
         indv1: &mut Individual<T>,
@@ -156,3 +425,153 @@ impl Crossover {
     }
 
 }
+
+/// Walk `node1` and `node2` simultaneously, recording every paired index that lies
+/// in their common region: positions reached by following the same path of child
+/// indices in both trees. Wherever the two nodes at a position have a different
+/// number of children, both of their entire subtrees are skipped.
+fn common_region<T: Tree>(
+    node1: &T,
+    node2: &T,
+    index1: &mut usize,
+    index2: &mut usize,
+    pairs: &mut Vec<(usize, usize)>,
+) {
+    let my_index1 = *index1;
+    let my_index2 = *index2;
+    *index1 += 1;
+    *index2 += 1;
+    pairs.push((my_index1, my_index2));
+
+    let children1 = node1.children();
+    let children2 = node2.children();
+    if children1.len() != children2.len() {
+        for child in &children1 {
+            *index1 += child.count_nodes();
+        }
+        for child in &children2 {
+            *index2 += child.count_nodes();
+        }
+        return;
+    }
+
+    for (child1, child2) in children1.iter().zip(children2.iter()) {
+        common_region(child1, child2, index1, index2, pairs);
+    }
+}
+
+/// Collect the indices of every internal (non-terminal) node if `internal` is true,
+/// or every terminal (leaf) node otherwise.
+fn category_indices<T: Tree>(tree: &mut BoxTree<T>, internal: bool) -> Vec<usize> {
+    let mut indices = Vec::new();
+    tree.map(|node, index, _| {
+        if (node.count_children() != 0) == internal {
+            indices.push(index);
+        }
+    });
+    indices
+}
+
+/// The height of the subtree rooted at `node`: `0` for a leaf, otherwise one more
+/// than the tallest child.
+fn subtree_height<T: Tree>(node: &T) -> usize {
+    node.children()
+        .iter()
+        .map(|child| subtree_height(child) + 1)
+        .max()
+        .unwrap_or(0)
+}
+
+/// Pick the bucket index `mate_size_fair` should draw a candidate from: the bucket
+/// for `target_size` itself if it has one, otherwise the nearest smaller non-empty
+/// bucket, falling back to a scan for the nearest non-empty bucket of any size if
+/// everything at or below `target_size` is empty.
+fn pick_fair_bucket(buckets: &[Vec<usize>], target_size: usize) -> usize {
+    let max_size = buckets.len() - 1;
+    let mut bucket_size = target_size.min(max_size);
+    while buckets[bucket_size].is_empty() {
+        if bucket_size == 0 {
+            return (0..=max_size)
+                .find(|&size| !buckets[size].is_empty())
+                .expect("a tree has at least one node");
+        }
+        bucket_size -= 1;
+    }
+    bucket_size
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gp::test_support::Toy;
+
+    #[test]
+    fn pick_fair_bucket_falls_back_to_nearest_smaller_bucket() {
+        let buckets = vec![vec![], vec![10], vec![], vec![]];
+        // target_size 3 and 2 are both empty; nearest non-empty below is bucket 1.
+        assert_eq!(pick_fair_bucket(&buckets, 3), 1);
+        assert_eq!(pick_fair_bucket(&buckets, 2), 1);
+        // target_size 1 is already populated, no fallback needed.
+        assert_eq!(pick_fair_bucket(&buckets, 1), 1);
+    }
+
+    #[test]
+    fn pick_fair_bucket_scans_upward_if_nothing_at_or_below_target() {
+        let buckets = vec![vec![], vec![], vec![7]];
+        // Nothing in buckets 0 or 1, so it has to scan up past the target.
+        assert_eq!(pick_fair_bucket(&buckets, 0), 2);
+    }
+
+    #[test]
+    fn mate_size_fair_runs_end_to_end_with_a_size_gap_between_parents() {
+        let mut rng = rand::thread_rng();
+        let mut indv1 = Individual::new_from_tree(Box::new(Toy::leaf()));
+        let indv2_tree = Toy::branch(vec![Toy::branch(vec![Toy::leaf(), Toy::leaf()])]);
+        let mut indv2 = Individual::new_from_tree(Box::new(indv2_tree));
+
+        let crossover = Crossover::size_fair();
+        crossover.mate(&mut indv1, &mut indv2, &mut rng);
+
+        assert_eq!(indv1.nodes_count(), indv1.tree.count_nodes());
+        assert_eq!(indv2.nodes_count(), indv2.tree.count_nodes());
+    }
+
+    #[test]
+    fn common_region_skips_mismatched_arity_subtrees() {
+        // indv1: root(arity 2) -> [ branch(arity 1) -> leaf, leaf ]
+        let t1 = Toy::branch(vec![Toy::branch(vec![Toy::leaf()]), Toy::leaf()]);
+        // indv2: root(arity 2) -> [ branch(arity 2) -> leaf, leaf, leaf ]
+        let t2 = Toy::branch(vec![
+            Toy::branch(vec![Toy::leaf(), Toy::leaf()]),
+            Toy::leaf(),
+        ]);
+
+        let mut pairs = Vec::new();
+        common_region(&t1, &t2, &mut 0, &mut 0, &mut pairs);
+
+        // Roots match (arity 2 == arity 2) -> (0, 0).
+        // First children mismatch (arity 1 vs arity 2) -> paired, but their own
+        // subtrees (indices 2 and 2..=3) are skipped entirely.
+        // Second children are both leaves (arity 0 == arity 0) -> paired.
+        assert_eq!(pairs, vec![(0, 0), (1, 1), (3, 4)]);
+    }
+
+    #[test]
+    fn mate_koza_reverts_when_every_attempt_would_exceed_max_depth() {
+        let mut rng = rand::thread_rng();
+        let tree1 = Toy::branch(vec![Toy::leaf(), Toy::leaf()]);
+        let tree2 = Toy::branch(vec![Toy::leaf()]);
+        let mut indv1 = Individual::new_from_tree(Box::new(tree1.clone()));
+        let mut indv2 = Individual::new_from_tree(Box::new(tree2.clone()));
+
+        // Both trees have exactly one internal node, each of height 1. With
+        // max_depth 0, swapping either internal node into the other tree always
+        // produces a subtree taller than the budget allows, so every attempt must
+        // be reverted and the parents must come out unchanged.
+        let crossover = Crossover::koza(1.0, 0.0, 0, 3);
+        crossover.mate(&mut indv1, &mut indv2, &mut rng);
+
+        assert_eq!(*indv1.tree, tree1);
+        assert_eq!(*indv2.tree, tree2);
+    }
+}
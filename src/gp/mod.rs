@@ -51,7 +51,19 @@ impl<T> Individual<T>
     pub fn recalculate_metadata(&mut self) {
         self.nodes_count = self.tree.count_nodes();
     }
-    
+
+    /// Return the subtree size (node count) rooted at each index position, in the
+    /// same traversal order `Crossover`/`Mutation` use to address nodes. Used by
+    /// operators such as `Crossover::size_fair` that need to pick nodes by size.
+    ///
+    /// Computed bottom-up in a single pass: each node's size is its children's
+    /// already-computed sizes plus one, not a fresh recursive count per node.
+    pub(crate) fn subtree_sizes(&self) -> Vec<usize> {
+        let mut sizes = vec![0; self.nodes_count];
+        fill_subtree_sizes(&self.tree, &mut 0, &mut sizes);
+        sizes
+    }
+
     /// Prune this individual's tree at max_depth.
     pub fn prune_at(&mut self, max_depth: usize) where T:Tree {
         use std::mem;
@@ -66,6 +78,61 @@ impl<T> Individual<T>
     }
 }
 
+/// Generate a population using the ramped half-and-half method.
+///
+/// Each individual's target depth is spread evenly ("ramped") across
+/// `[min_depth, max_depth]`, and within a given target depth half the individuals
+/// (rounding up) are grown with `TreeGen::perfect` and the rest with
+/// `TreeGen::full_ranged`: target depth and style are derived from the individual's
+/// position in the population rather than rolled at random, so unlike a single
+/// `TreeGen::half_and_half` generator reused for a whole population, the split is
+/// guaranteed rather than merely likely. A `perfect` individual has exactly its
+/// target depth; a `full_ranged` one uses it as a maximum and may come out shallower.
+///
+/// **This is the equivalent of DEAP's `genHalfAndHalf` applied across a population.**
+pub fn ramped_half_and_half<T, R>(
+    rng: &mut R,
+    config: &T::Config,
+    population_size: usize,
+    min_depth: usize,
+    max_depth: usize,
+) -> Vec<Individual<T>>
+where
+    T: Tree,
+    R: Rng,
+{
+    let depth_span = max_depth - min_depth + 1;
+    (0..population_size)
+        .map(|i| {
+            let depth = min_depth + i % depth_span;
+            // Individuals at the same depth are `depth_span` apart in `i`; alternate
+            // style on each successive lap through the ramp so the split per depth
+            // is guaranteed even, not just even in expectation.
+            let use_perfect = (i / depth_span) % 2 == 0;
+            let mut tg = if use_perfect {
+                TreeGen::perfect(&mut *rng, depth, depth)
+            } else {
+                TreeGen::full_ranged(&mut *rng, min_depth, depth)
+            };
+            Individual::new(&mut tg, config)
+        })
+        .collect()
+}
+
+/// Recursively fill in `sizes` with the subtree size rooted at each node, writing
+/// each one only after its children's sizes are already known.
+fn fill_subtree_sizes<T: Tree>(node: &T, index: &mut usize, sizes: &mut Vec<usize>) -> usize {
+    let my_index = *index;
+    *index += 1;
+    let size = 1 + node
+        .children()
+        .iter()
+        .map(|child| fill_subtree_sizes(child, index, sizes))
+        .sum::<usize>();
+    sizes[my_index] = size;
+    size
+}
+
 fn first_leaf<T>(node: &T) -> &T where T: Tree {
     let children = node.children();
     if !children.is_empty() {
@@ -83,3 +150,152 @@ impl<T> fmt::Display for Individual<T>
         write!(f, "{}", self.tree)
     }
 }
+
+/// A minimal `Tree` implementation, shared by the `gp` submodules' tests. Every node
+/// is just its own child count; this is enough to exercise operators that only care
+/// about tree shape (crossover, mutation, generation) without a real primitive set.
+#[cfg(test)]
+pub(crate) mod test_support {
+    use super::*;
+    use rand::Rng;
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Toy {
+        pub children: Vec<Toy>,
+    }
+
+    impl Toy {
+        pub fn leaf() -> Toy {
+            Toy { children: Vec::new() }
+        }
+
+        pub fn branch(children: Vec<Toy>) -> Toy {
+            Toy { children }
+        }
+
+        fn grow<R: Rng>(tg: &mut TreeGen<R>, config: &ToyConfig, depth: usize) -> Toy {
+            if tg.have_reached_a_leaf(depth) {
+                Toy::leaf()
+            } else {
+                let children = (0..config.arity)
+                    .map(|_| Toy::grow(tg, config, depth + 1))
+                    .collect();
+                Toy::branch(children)
+            }
+        }
+    }
+
+    /// Every non-leaf node generated by `Toy::tree` has this many children.
+    pub struct ToyConfig {
+        pub arity: usize,
+    }
+
+    impl Tree for Toy {
+        type Config = ToyConfig;
+
+        fn tree<R: Rng>(tg: &mut TreeGen<R>, config: &ToyConfig) -> BoxTree<Toy> {
+            Box::new(Toy::grow(tg, config, 0))
+        }
+
+        fn rand_node<R: Rng>(tg: &mut TreeGen<R>, config: &ToyConfig, arity: usize) -> Toy {
+            // Each child is its own freshly grown subtree, so depth 0 here means
+            // "this child", matching the convention `Toy::tree` uses for its root.
+            let children = (0..arity).map(|_| Toy::grow(tg, config, 0)).collect();
+            Toy::branch(children)
+        }
+
+        fn count_children(&self) -> usize {
+            self.children.len()
+        }
+
+        fn count_nodes(&self) -> usize {
+            1 + self.children.iter().map(Toy::count_nodes).sum::<usize>()
+        }
+
+        fn children(&self) -> Vec<&Toy> {
+            self.children.iter().collect()
+        }
+
+        fn map<F: FnMut(&mut Toy, usize, usize)>(&mut self, mut f: F) {
+            fn walk<F: FnMut(&mut Toy, usize, usize)>(
+                node: &mut Toy,
+                index: &mut usize,
+                depth: usize,
+                f: &mut F,
+            ) {
+                let my_index = *index;
+                *index += 1;
+                f(node, my_index, depth);
+                for child in &mut node.children {
+                    walk(child, index, depth + 1, f);
+                }
+            }
+            walk(self, &mut 0, 0, &mut f);
+        }
+
+        fn map_while<F: FnMut(&mut Toy, usize, usize) -> bool>(&mut self, mut f: F) {
+            fn walk<F: FnMut(&mut Toy, usize, usize) -> bool>(
+                node: &mut Toy,
+                index: &mut usize,
+                depth: usize,
+                f: &mut F,
+            ) -> bool {
+                let my_index = *index;
+                *index += 1;
+                if !f(node, my_index, depth) {
+                    return false;
+                }
+                for child in &mut node.children {
+                    if !walk(child, index, depth + 1, f) {
+                        return false;
+                    }
+                }
+                true
+            }
+            walk(self, &mut 0, 0, &mut f);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use self::test_support::{Toy, ToyConfig};
+
+    fn depth_of(node: &Toy) -> usize {
+        node.children
+            .iter()
+            .map(|child| depth_of(child) + 1)
+            .max()
+            .unwrap_or(0)
+    }
+
+    #[test]
+    fn ramped_half_and_half_ramps_depth_across_the_population() {
+        let mut rng = rand::thread_rng();
+        let config = ToyConfig { arity: 2 };
+        let min_depth = 1;
+        let max_depth = 3;
+        let depth_span = max_depth - min_depth + 1;
+
+        // Two full cycles through the depth span, so every target depth is hit twice.
+        let population = ramped_half_and_half(&mut rng, &config, depth_span * 2, min_depth, max_depth);
+
+        let mut seen_depths: Vec<usize> = population
+            .iter()
+            .map(|indv| depth_of(&indv.tree))
+            .collect();
+        seen_depths.sort();
+        seen_depths.dedup();
+
+        assert!(
+            seen_depths.iter().all(|&d| d <= max_depth),
+            "a tree exceeded max_depth"
+        );
+        assert!(
+            seen_depths.len() > 1,
+            "expected depths to vary across the ramp, got {:?}",
+            seen_depths
+        );
+    }
+}
@@ -0,0 +1,182 @@
+use gp::*;
+use rand::Rng;
+use std::mem;
+
+/// The mutation mode in use. See `Mutation`.
+#[derive(PartialEq, Clone, Copy, Debug)]
+enum MutationMode {
+    /// Corresponds to `Mutation::subtree`.
+    Subtree(usize),
+    /// Corresponds to `Mutation::point`.
+    Point(usize),
+}
+
+/// Configures mutation of a single GP individual.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct Mutation {
+    mode: MutationMode,
+}
+
+impl Mutation {
+    /// Get an operator that regenerates a randomly chosen subtree.
+    ///
+    /// The replacement subtree is grown with a depth budget of `max_depth` minus the
+    /// depth of the chosen node, so the mutated individual still respects `max_depth`
+    /// overall.
+    pub fn subtree(max_depth: usize) -> Mutation {
+        Mutation {
+            mode: MutationMode::Subtree(max_depth),
+        }
+    }
+
+    /// Get an operator that replaces the subtree at a randomly chosen node with a
+    /// freshly grown one whose root has the same arity as the chosen node (terminal
+    /// for terminal, function for function with an equal number of children).
+    ///
+    /// # Limitations
+    ///
+    /// Only the chosen node's arity is kept; the tree's shape below it is not:
+    /// `Tree::rand_node` returns a brand new `Self` together with new children, so
+    /// everything below the chosen node is regrown from scratch, not just its own
+    /// label. The replacement is given a depth budget of `max_depth` minus the depth
+    /// of the chosen node, the same way `Mutation::subtree` bounds its replacement.
+    pub fn point(max_depth: usize) -> Mutation {
+        Mutation {
+            mode: MutationMode::Point(max_depth),
+        }
+    }
+
+    /// Mutate an individual according to the configured mutation mode.
+    pub fn mutate<T, R>(
+        &self,
+        indv: &mut Individual<T>,
+        tree_gen: &mut TreeGen<R>,
+        config: &T::Config,
+        rng: &mut R,
+    ) where
+        T: Tree,
+        R: Rng,
+    {
+        match self.mode {
+            MutationMode::Subtree(max_depth) => {
+                self.mutate_subtree(indv, max_depth, tree_gen, config, rng)
+            }
+            MutationMode::Point(max_depth) => self.mutate_point(indv, max_depth, tree_gen, config, rng),
+        }
+    }
+
+    fn mutate_subtree<T, R>(
+        &self,
+        indv: &mut Individual<T>,
+        max_depth: usize,
+        tree_gen: &mut TreeGen<R>,
+        config: &T::Config,
+        rng: &mut R,
+    ) where
+        T: Tree,
+        R: Rng,
+    {
+        let target_index = rng.gen_range(0, indv.nodes_count());
+
+        let mut target_depth = 0;
+        indv.tree.map_while(|_, index, depth| {
+            if index == target_index {
+                target_depth = depth;
+                false
+            } else {
+                true
+            }
+        });
+
+        let remaining_depth = max_depth.saturating_sub(target_depth);
+        let mut scoped_gen = tree_gen.rescoped_full_ranged(0, remaining_depth);
+        let mut replacement = T::tree(&mut scoped_gen, config);
+
+        indv.tree.map_while(|node, index, _| {
+            if index == target_index {
+                mem::swap(node, &mut replacement);
+                false
+            } else {
+                true
+            }
+        });
+
+        indv.recalculate_metadata();
+    }
+
+    fn mutate_point<T, R>(
+        &self,
+        indv: &mut Individual<T>,
+        max_depth: usize,
+        tree_gen: &mut TreeGen<R>,
+        config: &T::Config,
+        rng: &mut R,
+    ) where
+        T: Tree,
+        R: Rng,
+    {
+        let target_index = rng.gen_range(0, indv.nodes_count());
+
+        let mut target_depth = 0;
+        indv.tree.map_while(|_, index, depth| {
+            if index == target_index {
+                target_depth = depth;
+                false
+            } else {
+                true
+            }
+        });
+        let remaining_depth = max_depth.saturating_sub(target_depth);
+
+        indv.tree.map_while(|node, index, _| {
+            if index == target_index {
+                let arity = node.count_children();
+                // Children are one level below the replaced node, so the budget left
+                // for *their* subtrees is one shallower than what was left for the node.
+                let mut scoped_gen = tree_gen.rescoped_full_ranged(0, remaining_depth.saturating_sub(1));
+                let mut replacement = T::rand_node(&mut scoped_gen, config, arity);
+                mem::swap(node, &mut replacement);
+                false
+            } else {
+                true
+            }
+        });
+
+        indv.recalculate_metadata();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gp::test_support::{Toy, ToyConfig};
+
+    fn depth_of(node: &Toy) -> usize {
+        node.children
+            .iter()
+            .map(|child| depth_of(child) + 1)
+            .max()
+            .unwrap_or(0)
+    }
+
+    #[test]
+    fn point_mutation_respects_remaining_depth_budget() {
+        let mut rng = rand::thread_rng();
+        let config = ToyConfig { arity: 2 };
+        let max_depth = 2;
+
+        // root (depth 0, arity 2) -> two leaves (depth 1); well within max_depth.
+        let root = Toy::branch(vec![Toy::leaf(), Toy::leaf()]);
+        let mut indv = Individual::new_from_tree(Box::new(root));
+
+        let mutation = Mutation::point(max_depth);
+        let mut tree_gen = TreeGen::full_ranged(&mut rng, 0, max_depth);
+        for _ in 0..20 {
+            mutation.mutate(&mut indv, &mut tree_gen, &config, &mut rng);
+            assert!(
+                depth_of(&indv.tree) <= max_depth,
+                "point mutation grew the tree past max_depth"
+            );
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use rand::{Rng, RngCore};
 
 /// The tree generation mode in use. See `TreeGen`.
-#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[derive(PartialEq, Clone, Copy, Debug)]
 enum TreeGenMode {
     /// Corresponds to `TreeGen::perfect`.
     Perfect(usize),
@@ -9,10 +9,13 @@ enum TreeGenMode {
     Full,
     /// Corresponds to `TreeGen::full_ranged`.
     FullRanged(usize),
+    /// Corresponds to `TreeGen::sized`. `p` is the per-node continuation probability
+    /// derived from the constructor's `expected_branch_size`.
+    Sized { desired_size: usize, p: f64 },
 }
 
 /// Configures depth and properties of GP trees.
-#[derive(PartialEq, Eq, Debug, Clone)]
+#[derive(PartialEq, Debug, Clone)]
 pub struct TreeGen<R>
 where
     R: Rng,
@@ -25,6 +28,9 @@ where
     min_depth: usize,
     /// The maximum depth of trees to generate.
     max_depth: usize,
+    /// Running count of nodes generated so far in the current tree. Only consulted by
+    /// `TreeGenMode::Sized`.
+    nodes_generated: usize,
 }
 
 impl<R> TreeGen<R>
@@ -42,6 +48,7 @@ where
             mode: TreeGenMode::Perfect(chosen_depth),
             min_depth: min_depth,
             max_depth: max_depth,
+            nodes_generated: 0,
         }
     }
 
@@ -55,6 +62,7 @@ where
             mode: TreeGenMode::Full,
             min_depth: min_depth,
             max_depth: max_depth,
+            nodes_generated: 0,
         }
     }
 
@@ -69,13 +77,49 @@ where
             mode: TreeGenMode::FullRanged(chosen_depth),
             min_depth: min_depth,
             max_depth: max_depth,
+            nodes_generated: 0,
+        }
+    }
+
+    /// Generate trees targeting a desired total node count rather than a fixed depth.
+    ///
+    /// `expected_branch_size` is the average arity of non-terminal nodes; it sets the
+    /// per-node continuation probability `p = 1 - 1 / expected_branch_size` used to
+    /// decide, at each expansion, whether to keep growing. Every node is also made a
+    /// leaf once `desired_size` nodes have been placed in the current tree, or at
+    /// `max_depth`, whichever is reached first; siblings already committed to when
+    /// the count is reached are still placed (as leaves), so a tree can overshoot
+    /// `desired_size` slightly. The count restarts at each root (depth 0).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `expected_branch_size <= 1.0`: an average arity of 1 or less can
+    /// never branch (every non-terminal would be a chain or worse), and `p` would be
+    /// zero (no node is ever expanded) or negative (not a probability at all).
+    pub fn sized(rng: R, desired_size: usize, expected_branch_size: f64, max_depth: usize) -> TreeGen<R> {
+        assert!(
+            expected_branch_size > 1.0,
+            "TreeGen::sized requires expected_branch_size > 1.0 (got {}); \
+             use TreeGen::perfect or TreeGen::full_ranged for non-branching trees",
+            expected_branch_size
+        );
+        let p = 1.0 - 1.0 / expected_branch_size;
+        TreeGen {
+            rng: rng,
+            mode: TreeGenMode::Sized { desired_size, p },
+            min_depth: 0,
+            max_depth: max_depth,
+            nodes_generated: 0,
         }
     }
 
     /// Randomly choose between `TreeGen::perfect` and `TreeGen::full_ranged`.
     ///
+    /// The choice is made once, here, so every individual built from a single
+    /// `TreeGen` returned by this constructor comes out in the same style. To vary
+    /// style and depth per individual across a population, use `gp::ramped_half_and_half`.
+    ///
     /// **This is the equivalent of DEAP's `genHalfAndHalf`.**
-    // @TODO: This choice needs to happen at runtime.
     pub fn half_and_half(mut rng: R, min_depth: usize, max_depth: usize) -> TreeGen<R> {
         if rng.gen() {
             Self::perfect(rng, min_depth, max_depth)
@@ -84,6 +128,13 @@ where
         }
     }
 
+    /// Build a new generator that reuses this one's randomness but targets a fresh
+    /// `full_ranged` depth range. Used by `Mutation::subtree` to regenerate a branch
+    /// without exceeding the individual's overall depth bound.
+    pub(crate) fn rescoped_full_ranged(&mut self, min_depth: usize, max_depth: usize) -> TreeGen<&mut R> {
+        TreeGen::full_ranged(&mut self.rng, min_depth, max_depth)
+    }
+
     /// Chooses whether to generate a Leaf node. Used by `Tree::child`.
     pub fn have_reached_a_leaf(&mut self, current_depth: usize) -> bool {
         match self.mode {
@@ -108,6 +159,16 @@ where
                     || (current_depth >= self.min_depth)
                         && self.gen_bool(1.0 / depth_interval as f64)
             }
+            TreeGenMode::Sized { desired_size, p } => {
+                // A root starts a new tree, so its node budget starts afresh.
+                if current_depth == 0 {
+                    self.nodes_generated = 0;
+                }
+                self.nodes_generated += 1;
+                current_depth == self.max_depth
+                    || self.nodes_generated >= desired_size
+                    || self.gen_bool(1.0 - p)
+            }
         }
     }
 }
@@ -133,3 +194,39 @@ where
         self.rng.try_fill_bytes(dest)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gp::test_support::{Toy, ToyConfig};
+    use gp::tree::Tree;
+
+    fn depth_of(node: &Toy) -> usize {
+        node.children
+            .iter()
+            .map(|child| depth_of(child) + 1)
+            .max()
+            .unwrap_or(0)
+    }
+
+    #[test]
+    fn sized_generation_honors_desired_size_and_max_depth() {
+        let config = ToyConfig { arity: 3 };
+        let desired_size = 10;
+        let max_depth = 4;
+
+        let mut tg = TreeGen::sized(rand::thread_rng(), desired_size, 3.0, max_depth);
+        let tree = Toy::tree(&mut tg, &config);
+
+        assert!(depth_of(&tree) <= max_depth);
+        // Generation can only overshoot by the handful of siblings already
+        // committed to at the moment the running count crosses the target.
+        assert!(tree.count_nodes() <= desired_size * 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn sized_rejects_non_branching_expected_size() {
+        TreeGen::<rand::ThreadRng>::sized(rand::thread_rng(), 10, 1.0, 5);
+    }
+}